@@ -0,0 +1,3 @@
+pub mod permission_confirmation;
+pub mod permission_policy;
+pub mod permission_store;
@@ -1,3 +1,6 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -5,6 +8,23 @@ pub enum Permission {
     AlwaysAllow,
     AllowOnce,
     DenyOnce,
+    AllowForSession,
+    AllowUntil(DateTime<Utc>),
+}
+
+impl Permission {
+    /// Whether this grant still holds, given the current time and whether
+    /// the originating session is still open. `AllowOnce`/`DenyOnce` never
+    /// outlive the call they were issued for, so they are treated as expired
+    /// here.
+    pub fn is_valid(&self, now: DateTime<Utc>, session_ended: bool) -> bool {
+        match self {
+            Permission::AlwaysAllow => true,
+            Permission::AllowOnce | Permission::DenyOnce => false,
+            Permission::AllowForSession => !session_ended,
+            Permission::AllowUntil(deadline) => now < *deadline,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -13,9 +33,327 @@ pub enum PrincipalType {
     Tool,
 }
 
+/// A predicate narrowing a grant to a subset of a principal's invocations,
+/// e.g. `AlwaysAllow` a shell tool for `git *` while still prompting for
+/// `rm *`. `None` on a confirmation means the grant is unscoped (matches any
+/// invocation), preserving today's all-or-nothing behavior.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum ScopePredicate {
+    /// Glob pattern matched against the invocation's argument string.
+    ArgGlob(String),
+    /// Path prefix matched against a filesystem-path argument.
+    PathPrefix(String),
+    /// Host matched against a network-facing argument.
+    Host(String),
+}
+
+impl ScopePredicate {
+    pub fn matches(&self, argument: &str) -> bool {
+        match self {
+            ScopePredicate::ArgGlob(pattern) => glob_match(pattern, argument),
+            ScopePredicate::PathPrefix(prefix) => argument.starts_with(prefix.as_str()),
+            ScopePredicate::Host(host) => argument == host,
+        }
+    }
+
+    /// How narrowly this predicate matches, used to rank overlapping rules —
+    /// higher is more specific. A predicate with more fixed (non-wildcard)
+    /// characters matches a smaller set of arguments, so it wins over a
+    /// broader one (e.g. `git push *` over `git *`).
+    fn specificity(&self) -> usize {
+        match self {
+            ScopePredicate::ArgGlob(pattern) => pattern.chars().filter(|c| *c != '*').count(),
+            ScopePredicate::PathPrefix(prefix) => prefix.len(),
+            ScopePredicate::Host(host) => host.len(),
+        }
+    }
+}
+
+/// Minimal `*`-only glob matcher, sufficient for patterns like `git *`.
+fn glob_match(pattern: &str, value: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == value,
+        Some((prefix, suffix)) => {
+            value.len() >= prefix.len() + suffix.len()
+                && value.starts_with(prefix)
+                && value.ends_with(suffix)
+        }
+    }
+}
+
+/// A named capability a tool call can exercise, OAuth-scope-inspired so a
+/// principal can be granted `Read` without also being granted `Write`,
+/// `Network`, or `Exec`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub enum Scope {
+    Read,
+    Write,
+    Network,
+    Exec,
+    Custom(String),
+}
+
+/// The set of scopes granted to a principal, each carrying its own
+/// `Permission` rather than one blanket decision for the whole principal.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GrantedScopes {
+    granted: HashMap<Scope, Permission>,
+}
+
+impl GrantedScopes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn grant(&mut self, scope: Scope, permission: Permission) {
+        self.granted.insert(scope, permission);
+    }
+
+    /// The permission granted for `required`, or `None` when the scope was
+    /// never granted, or a time-/session-scoped grant (`AllowForSession`,
+    /// `AllowUntil`) for it has since expired, or it was granted `AllowOnce`
+    /// or `DenyOnce` (which `Permission::is_valid` never treats as standing) — mirroring
+    /// `PermissionPolicy::resolve`'s contract, the caller falls through to
+    /// prompting the user rather than treating either case as a hard deny.
+    pub fn grants(
+        &self,
+        required: &Scope,
+        now: DateTime<Utc>,
+        session_ended: bool,
+    ) -> Option<Permission> {
+        self.granted
+            .get(required)
+            .filter(|permission| permission.is_valid(now, session_ended))
+            .cloned()
+    }
+
+    pub fn scopes(&self) -> HashSet<Scope> {
+        self.granted.keys().cloned().collect()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PermissionConfirmation {
     pub principal_name: String,
     pub principal_type: PrincipalType,
     pub permission: Permission,
+    pub granted_at: DateTime<Utc>,
+    /// Added after on-disk persistence (chunk0-4) existed; `#[serde(default)]`
+    /// keeps stores written before these fields existed loadable instead of
+    /// failing to deserialize.
+    #[serde(default)]
+    pub scope: Option<ScopePredicate>,
+    #[serde(default)]
+    pub granted_scopes: GrantedScopes,
+}
+
+impl PermissionConfirmation {
+    pub fn new(
+        principal_name: String,
+        principal_type: PrincipalType,
+        permission: Permission,
+    ) -> Self {
+        Self {
+            principal_name,
+            principal_type,
+            permission,
+            granted_at: Utc::now(),
+            scope: None,
+            granted_scopes: GrantedScopes::new(),
+        }
+    }
+
+    pub fn with_scope(mut self, scope: ScopePredicate) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Whether this grant still holds, given the current time and whether the
+    /// originating session is still open. See `Permission::is_valid`.
+    pub fn is_valid(&self, now: DateTime<Utc>, session_ended: bool) -> bool {
+        self.permission.is_valid(now, session_ended)
+    }
+}
+
+/// Finds the most specific rule matching an incoming tool call, given the
+/// principal's stored confirmations and the argument the call was made with.
+/// Scoped rules (whose predicate matches) win over unscoped, all-or-nothing
+/// rules; among several matching scoped rules, the one with the highest
+/// `ScopePredicate::specificity` wins (e.g. `git push *` over `git *`), so
+/// the result doesn't depend on storage order. Between equally specific
+/// matching rules, and between several unscoped rules, the first one stored
+/// wins. Returns `None` when nothing matches, signalling the caller should
+/// prompt the user.
+pub fn resolve_scoped_permission<'a>(
+    confirmations: impl IntoIterator<Item = &'a PermissionConfirmation>,
+    argument: &str,
+) -> Option<&'a PermissionConfirmation> {
+    let mut best: Option<(&PermissionConfirmation, usize)> = None;
+    let mut fallback: Option<&PermissionConfirmation> = None;
+    for confirmation in confirmations {
+        match &confirmation.scope {
+            Some(scope) if scope.matches(argument) => {
+                let specificity = scope.specificity();
+                let is_more_specific = match best {
+                    Some((_, best_specificity)) => specificity > best_specificity,
+                    None => true,
+                };
+                if is_more_specific {
+                    best = Some((confirmation, specificity));
+                }
+            }
+            Some(_) => continue,
+            None => {
+                fallback.get_or_insert(confirmation);
+            }
+        }
+    }
+    best.map(|(confirmation, _)| confirmation).or(fallback)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn is_valid_treats_once_grants_as_never_standing() {
+        let grant = PermissionConfirmation::new(
+            "developer__shell".to_string(),
+            PrincipalType::Tool,
+            Permission::AllowOnce,
+        );
+        assert!(!grant.is_valid(Utc::now(), false));
+
+        let grant = PermissionConfirmation::new(
+            "developer__shell".to_string(),
+            PrincipalType::Tool,
+            Permission::DenyOnce,
+        );
+        assert!(!grant.is_valid(Utc::now(), false));
+    }
+
+    #[test]
+    fn is_valid_always_allow_never_expires() {
+        let grant = PermissionConfirmation::new(
+            "developer__shell".to_string(),
+            PrincipalType::Tool,
+            Permission::AlwaysAllow,
+        );
+        assert!(grant.is_valid(Utc::now(), true));
+    }
+
+    #[test]
+    fn is_valid_for_session_expires_when_session_ends() {
+        let grant = PermissionConfirmation::new(
+            "developer__shell".to_string(),
+            PrincipalType::Tool,
+            Permission::AllowForSession,
+        );
+        assert!(grant.is_valid(Utc::now(), false));
+        assert!(!grant.is_valid(Utc::now(), true));
+    }
+
+    #[test]
+    fn is_valid_until_deadline_expires_after_deadline() {
+        let now = Utc::now();
+        let grant = PermissionConfirmation::new(
+            "developer__shell".to_string(),
+            PrincipalType::Tool,
+            Permission::AllowUntil(now + Duration::seconds(1)),
+        );
+        assert!(grant.is_valid(now, false));
+        assert!(!grant.is_valid(now + Duration::seconds(2), false));
+    }
+
+    #[test]
+    fn glob_match_handles_prefix_suffix_and_exact() {
+        assert!(glob_match("git *", "git push origin main"));
+        assert!(!glob_match("git *", "rm -rf /"));
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(glob_match("exact", "exact"));
+        assert!(!glob_match("exact", "exact-ish"));
+    }
+
+    fn scoped(principal: &str, predicate: ScopePredicate) -> PermissionConfirmation {
+        PermissionConfirmation::new(
+            principal.to_string(),
+            PrincipalType::Tool,
+            Permission::AlwaysAllow,
+        )
+        .with_scope(predicate)
+    }
+
+    #[test]
+    fn resolve_scoped_permission_prefers_most_specific_match() {
+        let broad = scoped("developer__shell", ScopePredicate::ArgGlob("git *".into()));
+        let narrow = scoped(
+            "developer__shell",
+            ScopePredicate::ArgGlob("git push *".into()),
+        );
+        // Storage order shouldn't matter: narrow first...
+        let resolved = resolve_scoped_permission([&narrow, &broad], "git push origin main");
+        assert_eq!(resolved.unwrap().scope, narrow.scope);
+        // ...and broad first.
+        let resolved = resolve_scoped_permission([&broad, &narrow], "git push origin main");
+        assert_eq!(resolved.unwrap().scope, narrow.scope);
+    }
+
+    #[test]
+    fn resolve_scoped_permission_falls_back_to_unscoped_rule() {
+        let unscoped = PermissionConfirmation::new(
+            "developer__shell".to_string(),
+            PrincipalType::Tool,
+            Permission::AllowOnce,
+        );
+        let resolved = resolve_scoped_permission([&unscoped], "rm -rf /");
+        assert_eq!(resolved.unwrap().permission, Permission::AllowOnce);
+    }
+
+    #[test]
+    fn resolve_scoped_permission_returns_none_when_nothing_matches() {
+        let scoped = scoped("developer__shell", ScopePredicate::ArgGlob("git *".into()));
+        assert!(resolve_scoped_permission([&scoped], "rm -rf /").is_none());
+    }
+
+    #[test]
+    fn granted_scopes_returns_granted_permission() {
+        let mut scopes = GrantedScopes::new();
+        scopes.grant(Scope::Read, Permission::AlwaysAllow);
+        assert_eq!(
+            scopes.grants(&Scope::Read, Utc::now(), false),
+            Some(Permission::AlwaysAllow)
+        );
+    }
+
+    #[test]
+    fn granted_scopes_falls_through_to_prompt_when_ungranted() {
+        let mut scopes = GrantedScopes::new();
+        scopes.grant(Scope::Read, Permission::AlwaysAllow);
+        assert_eq!(scopes.grants(&Scope::Write, Utc::now(), false), None);
+    }
+
+    #[test]
+    fn granted_scopes_falls_through_to_prompt_when_expired() {
+        let mut scopes = GrantedScopes::new();
+        scopes.grant(Scope::Network, Permission::AllowForSession);
+        assert_eq!(
+            scopes.grants(&Scope::Network, Utc::now(), false),
+            Some(Permission::AllowForSession)
+        );
+        assert_eq!(scopes.grants(&Scope::Network, Utc::now(), true), None);
+    }
+
+    #[test]
+    fn granted_scopes_regrant_replaces_prior_decision() {
+        let mut scopes = GrantedScopes::new();
+        scopes.grant(Scope::Exec, Permission::DenyOnce);
+        scopes.grant(Scope::Exec, Permission::AlwaysAllow);
+        assert_eq!(
+            scopes.grants(&Scope::Exec, Utc::now(), false),
+            Some(Permission::AlwaysAllow)
+        );
+        assert_eq!(scopes.scopes().len(), 1);
+    }
 }
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+
+use super::permission_confirmation::{Permission, PrincipalType};
+
+/// A single rule in a `PermissionPolicy`: an admin-configured decision for a
+/// specific principal, or an explicit pass-through to the interactive prompt.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PolicyRule {
+    pub principal_name: String,
+    pub principal_type: PrincipalType,
+    pub decision: PolicyDecision,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    Force(Permission),
+    AskUser,
+}
+
+/// An ordered, enterprise-managed allow/deny list loaded at startup and
+/// consulted before any interactive `PermissionConfirmation` is requested.
+/// Rules are evaluated in order; the first matching rule wins.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PermissionPolicy {
+    pub rules: Vec<PolicyRule>,
+}
+
+impl PermissionPolicy {
+    pub fn new(rules: Vec<PolicyRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Resolves a principal to a forced permission, or `None` if the policy
+    /// has no opinion and the caller should fall through to asking the user.
+    pub fn resolve(
+        &self,
+        principal_name: &str,
+        principal_type: &PrincipalType,
+    ) -> Option<Permission> {
+        for rule in &self.rules {
+            if rule.principal_name == principal_name && &rule.principal_type == principal_type {
+                return match &rule.decision {
+                    PolicyDecision::Force(permission) => Some(permission.clone()),
+                    PolicyDecision::AskUser => None,
+                };
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::permission_confirmation::Permission;
+    use super::*;
+
+    #[test]
+    fn resolve_forces_permission_for_matching_rule() {
+        let policy = PermissionPolicy::new(vec![PolicyRule {
+            principal_name: "dangerous_extension".to_string(),
+            principal_type: PrincipalType::Extention,
+            decision: PolicyDecision::Force(Permission::DenyOnce),
+        }]);
+
+        assert_eq!(
+            policy.resolve("dangerous_extension", &PrincipalType::Extention),
+            Some(Permission::DenyOnce)
+        );
+    }
+
+    #[test]
+    fn resolve_falls_through_to_ask_user_rule() {
+        let policy = PermissionPolicy::new(vec![PolicyRule {
+            principal_name: "developer__shell".to_string(),
+            principal_type: PrincipalType::Tool,
+            decision: PolicyDecision::AskUser,
+        }]);
+
+        assert_eq!(
+            policy.resolve("developer__shell", &PrincipalType::Tool),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_returns_none_when_no_rule_matches() {
+        let policy = PermissionPolicy::new(vec![PolicyRule {
+            principal_name: "dangerous_extension".to_string(),
+            principal_type: PrincipalType::Extention,
+            decision: PolicyDecision::Force(Permission::DenyOnce),
+        }]);
+
+        assert_eq!(
+            policy.resolve("some_other_tool", &PrincipalType::Tool),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_does_not_cross_principal_types() {
+        let policy = PermissionPolicy::new(vec![PolicyRule {
+            principal_name: "shared_name".to_string(),
+            principal_type: PrincipalType::Extention,
+            decision: PolicyDecision::Force(Permission::AlwaysAllow),
+        }]);
+
+        assert_eq!(policy.resolve("shared_name", &PrincipalType::Tool), None);
+    }
+}
@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::permission_confirmation::{Permission, PermissionConfirmation, PrincipalType};
+
+/// One entry in the append-only audit trail: a record of a decision being
+/// made, independent of whether the resulting grant is later revoked.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub principal_name: String,
+    pub principal_type: PrincipalType,
+    pub permission: Permission,
+    pub decided_at: DateTime<Utc>,
+}
+
+impl From<&PermissionConfirmation> for AuditEntry {
+    fn from(confirmation: &PermissionConfirmation) -> Self {
+        Self {
+            principal_name: confirmation.principal_name.clone(),
+            principal_type: confirmation.principal_type.clone(),
+            permission: confirmation.permission.clone(),
+            decided_at: confirmation.granted_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct PermissionStoreData {
+    grants: Vec<PermissionConfirmation>,
+    audit_log: Vec<AuditEntry>,
+}
+
+/// Persists accumulated `PermissionConfirmation` decisions to disk and keeps
+/// an append-only audit trail of every decision made, so a user can review
+/// "what have I trusted" across restarts and rescind a stale `AlwaysAllow`.
+pub struct PermissionStore {
+    path: PathBuf,
+    data: PermissionStoreData,
+}
+
+impl PermissionStore {
+    /// Loads the store from `path`, starting empty if the file doesn't exist.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let data = if path.exists() {
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("failed to read permission store at {:?}", path))?;
+            serde_json::from_str(&contents)
+                .with_context(|| format!("failed to parse permission store at {:?}", path))?
+        } else {
+            PermissionStoreData::default()
+        };
+        Ok(Self { path, data })
+    }
+
+    /// Records a new grant and appends it to the audit log, then persists.
+    /// A principal can hold several scoped grants at once (chunk0-3), so this
+    /// only replaces an existing grant with an identical (principal_name,
+    /// principal_type, scope) key, rather than collapsing all of a
+    /// principal's grants into one, and without conflating an `Extention`
+    /// and a `Tool` that happen to share a name.
+    pub fn record(&mut self, confirmation: PermissionConfirmation) -> Result<()> {
+        self.data.audit_log.push(AuditEntry::from(&confirmation));
+        self.data.grants.retain(|existing| {
+            existing.principal_name != confirmation.principal_name
+                || existing.principal_type != confirmation.principal_type
+                || existing.scope != confirmation.scope
+        });
+        self.data.grants.push(confirmation);
+        self.save()
+    }
+
+    /// Lists currently standing grants, excluding ones that have expired
+    /// (e.g. a lapsed `AllowUntil` or an ended `AllowForSession`) so "what
+    /// have I trusted" doesn't show stale grants as still in effect.
+    pub fn list(&self, now: DateTime<Utc>, session_ended: bool) -> Vec<&PermissionConfirmation> {
+        self.data
+            .grants
+            .iter()
+            .filter(|grant| grant.is_valid(now, session_ended))
+            .collect()
+    }
+
+    /// Lists all standing grants, expired or not, for maintenance tasks that
+    /// need to see the raw stored state rather than what currently applies.
+    pub fn list_all(&self) -> &[PermissionConfirmation] {
+        &self.data.grants
+    }
+
+    /// Revokes all standing grants for a principal, returning whether any
+    /// existed. Matches on both `principal_name` and `principal_type` so
+    /// revoking a `Tool` never evicts an `Extention` of the same name.
+    pub fn revoke(&mut self, principal_name: &str, principal_type: &PrincipalType) -> Result<bool> {
+        let before = self.data.grants.len();
+        self.data.grants.retain(|grant| {
+            grant.principal_name != principal_name || &grant.principal_type != principal_type
+        });
+        let revoked = self.data.grants.len() != before;
+        if revoked {
+            self.save()?;
+        }
+        Ok(revoked)
+    }
+
+    /// Exports the full audit trail, e.g. for a compliance review.
+    pub fn export_audit_log(&self) -> &[AuditEntry] {
+        &self.data.audit_log
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create directory {:?}", parent))?;
+        }
+        let contents = serde_json::to_string_pretty(&self.data)
+            .context("failed to serialize permission store")?;
+        fs::write(&self.path, contents)
+            .with_context(|| format!("failed to write permission store at {:?}", self.path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use chrono::Duration;
+
+    use super::super::permission_confirmation::ScopePredicate;
+    use super::*;
+
+    fn temp_store_path(name: &str) -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let unique = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!("goose-permission-store-test-{name}-{unique}.json"))
+    }
+
+    fn grant(principal: &str, permission: Permission) -> PermissionConfirmation {
+        PermissionConfirmation::new(principal.to_string(), PrincipalType::Tool, permission)
+    }
+
+    #[test]
+    fn record_and_revoke_round_trip() {
+        let path = temp_store_path("round-trip");
+        let mut store = PermissionStore::load(&path).unwrap();
+        store
+            .record(grant("developer__shell", Permission::AlwaysAllow))
+            .unwrap();
+        assert_eq!(store.list_all().len(), 1);
+        assert_eq!(store.export_audit_log().len(), 1);
+
+        let reloaded = PermissionStore::load(&path).unwrap();
+        assert_eq!(reloaded.list_all().len(), 1);
+
+        let mut store = reloaded;
+        assert!(store
+            .revoke("developer__shell", &PrincipalType::Tool)
+            .unwrap());
+        assert_eq!(store.list_all().len(), 0);
+        // The audit trail is append-only: revoking doesn't erase history.
+        assert_eq!(store.export_audit_log().len(), 1);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn record_and_revoke_do_not_cross_principal_types() {
+        let path = temp_store_path("cross-type");
+        let mut store = PermissionStore::load(&path).unwrap();
+        store
+            .record(grant("shell", Permission::AlwaysAllow))
+            .unwrap();
+        store
+            .record(PermissionConfirmation::new(
+                "shell".to_string(),
+                PrincipalType::Extention,
+                Permission::DenyOnce,
+            ))
+            .unwrap();
+
+        // Same name, different principal_type: both grants must survive.
+        assert_eq!(store.list_all().len(), 2);
+
+        assert!(store.revoke("shell", &PrincipalType::Tool).unwrap());
+        let remaining = store.list_all();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].principal_type, PrincipalType::Extention);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn record_keeps_distinct_scoped_grants_for_same_principal() {
+        let path = temp_store_path("scoped-grants");
+        let mut store = PermissionStore::load(&path).unwrap();
+        store
+            .record(
+                grant("developer__shell", Permission::AlwaysAllow)
+                    .with_scope(ScopePredicate::ArgGlob("git *".into())),
+            )
+            .unwrap();
+        store
+            .record(
+                grant("developer__shell", Permission::DenyOnce)
+                    .with_scope(ScopePredicate::ArgGlob("rm *".into())),
+            )
+            .unwrap();
+
+        assert_eq!(store.list_all().len(), 2);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn list_excludes_expired_grants() {
+        let path = temp_store_path("expired");
+        let mut store = PermissionStore::load(&path).unwrap();
+        let now = Utc::now();
+        store
+            .record(grant(
+                "developer__shell",
+                Permission::AllowUntil(now - Duration::seconds(1)),
+            ))
+            .unwrap();
+        store
+            .record(grant("developer__editor", Permission::AlwaysAllow))
+            .unwrap();
+
+        let standing = store.list(now, false);
+        assert_eq!(standing.len(), 1);
+        assert_eq!(standing[0].principal_name, "developer__editor");
+        assert_eq!(store.list_all().len(), 2);
+
+        fs::remove_file(&path).ok();
+    }
+}